@@ -0,0 +1,191 @@
+use std::f64::consts::PI;
+
+/// Sample rate whisper expects its input audio at.
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+/// Half-width (in source samples) of the windowed-sinc kernel used for resampling.
+const HALF_TAPS: i64 = 16;
+/// Kaiser window shape parameter; higher values trade passband ripple for wider transition.
+const KAISER_BETA: f64 = 6.0;
+
+/// Loads a WAV file, downmixes it to mono, and resamples it to [`TARGET_SAMPLE_RATE`].
+///
+/// Returns the resulting f32 samples (ready to hand to whisper) along with the
+/// source file's original sample rate, so callers can report what was detected.
+pub fn load_and_resample(wav_path: &str) -> Result<(Vec<f32>, u32), String> {
+    let mut reader =
+        hound::WavReader::open(wav_path).map_err(|e| format!("Failed to open wav: {e}"))?;
+    let spec = reader.spec();
+
+    let raw_samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let ints: Vec<i32> = reader
+                .samples::<i32>()
+                .map(|x| x.map_err(|e| format!("Failed to read sample: {e}")))
+                .collect::<Result<Vec<_>, _>>()?;
+            let max_value = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            ints.into_iter().map(|s| s as f32 / max_value).collect()
+        }
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|x| x.map_err(|e| format!("Failed to read sample: {e}")))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let mono = downmix(&raw_samples, spec.channels as usize);
+    let resampled = resample(&mono, spec.sample_rate, TARGET_SAMPLE_RATE);
+
+    Ok((resampled, spec.sample_rate))
+}
+
+/// Averages interleaved channels down to a single mono channel.
+fn downmix(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resamples `input` from `source_rate` to `target_rate` using a Kaiser-windowed
+/// sinc kernel evaluated directly at each output position (a polyphase filter
+/// specialized to one output sample per call).
+///
+/// When downsampling, the kernel's cutoff is scaled down to the target Nyquist
+/// (and its support widened to match) so content above it is filtered out
+/// instead of aliasing back into the audible band.
+fn resample(input: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
+    if source_rate == target_rate || input.is_empty() {
+        return input.to_vec();
+    }
+
+    let ratio = target_rate as f64 / source_rate as f64;
+    let out_len = ((input.len() as f64) * ratio).round() as usize;
+
+    let cutoff_scale = ratio.min(1.0);
+    let half_taps = (HALF_TAPS as f64 / cutoff_scale).ceil() as i64;
+
+    (0..out_len)
+        .map(|n| sinc_interpolate(input, n as f64 / ratio, cutoff_scale, half_taps))
+        .collect()
+}
+
+/// Reconstructs the signal value at fractional source position `t` by convolving
+/// the neighboring `2 * half_taps` samples with a Kaiser-windowed sinc kernel
+/// whose cutoff is scaled by `cutoff_scale` (1.0 when upsampling or unchanged
+/// rate; `target_rate / source_rate` when downsampling, to band-limit the
+/// signal to the new Nyquist before it's resampled). Source positions outside
+/// the signal are clamped to the nearest edge sample.
+fn sinc_interpolate(input: &[f32], t: f64, cutoff_scale: f64, half_taps: i64) -> f32 {
+    let center = t.floor() as i64;
+    let lo = center - half_taps + 1;
+    let hi = center + half_taps;
+    let last = input.len() as i64 - 1;
+
+    let mut acc = 0.0f64;
+    for i in lo..=hi {
+        let idx = i.clamp(0, last) as usize;
+        let x = t - i as f64;
+        acc += input[idx] as f64 * kaiser_windowed_sinc(x, cutoff_scale, half_taps as f64);
+    }
+    acc as f32
+}
+
+fn kaiser_windowed_sinc(x: f64, cutoff_scale: f64, half_taps: f64) -> f64 {
+    let scaled_x = x * cutoff_scale;
+    let sinc = if scaled_x.abs() < 1e-9 {
+        1.0
+    } else {
+        (PI * scaled_x).sin() / (PI * scaled_x)
+    };
+
+    let window = if x.abs() >= half_taps {
+        0.0
+    } else {
+        let ratio = x / half_taps;
+        bessel_i0(KAISER_BETA * (1.0 - ratio * ratio).sqrt()) / bessel_i0(KAISER_BETA)
+    };
+
+    sinc * cutoff_scale * window
+}
+
+/// Modified Bessel function of the first kind, order 0, via its power series.
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    while term > sum * 1e-12 {
+        term *= (x / (2.0 * k)).powi(2);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    fn sine(freq: f64, rate: u32, n: usize) -> Vec<f32> {
+        (0..n)
+            .map(|i| (2.0 * PI * freq * i as f64 / rate as f64).sin() as f32)
+            .collect()
+    }
+
+    #[test]
+    fn sinc_interpolate_reconstructs_exact_samples() {
+        let input = [0.1f32, -0.2, 0.3, 0.4, -0.5, 0.25, -0.1, 0.05];
+        for (i, &expected) in input.iter().enumerate() {
+            let got = sinc_interpolate(&input, i as f64, 1.0, HALF_TAPS);
+            assert!(
+                (got - expected).abs() < 1e-4,
+                "index {i}: got {got}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn resample_is_a_no_op_when_rates_match() {
+        let input = vec![0.1f32, 0.2, -0.3];
+        assert_eq!(resample(&input, 16_000, 16_000), input);
+    }
+
+    #[test]
+    fn downsampling_attenuates_frequencies_above_target_nyquist() {
+        let source_rate = 48_000;
+        let target_rate = 16_000; // Nyquist = 8 kHz
+        let input = sine(10_000.0, source_rate, 4_800); // above target Nyquist
+        let output = resample(&input, source_rate, target_rate);
+
+        let input_rms = rms(&input);
+        let output_rms = rms(&output);
+        assert!(
+            output_rms < input_rms * 0.3,
+            "expected the 10kHz tone to be attenuated by the anti-aliasing filter, \
+             got input_rms={input_rms} output_rms={output_rms}"
+        );
+    }
+
+    #[test]
+    fn downsampling_preserves_frequencies_within_target_nyquist() {
+        let source_rate = 48_000;
+        let target_rate = 16_000; // Nyquist = 8 kHz
+        let input = sine(1_000.0, source_rate, 4_800); // well within target Nyquist
+        let output = resample(&input, source_rate, target_rate);
+
+        let input_rms = rms(&input);
+        let output_rms = rms(&output);
+        assert!(
+            output_rms > input_rms * 0.8,
+            "expected the 1kHz tone to pass through mostly intact, \
+             got input_rms={input_rms} output_rms={output_rms}"
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use serde::Deserialize;
-// declare the module
+use tauri::Emitter;
+// declare the modules
+mod audio;
+mod silence;
 mod transcribe;
-// bring the function into scope (or call it with `transcribe::transcribe_file`)
-use crate::transcribe::transcribe_file;
+// bring the functions into scope (or call them with `transcribe::transcribe_file`)
+use crate::transcribe::{transcribe_batch, transcribe_file, transcribe_file_streaming};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +15,11 @@ struct TranscriptionRequest {
     model: String,
     max_segment_length: u32,
     max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,6 +28,8 @@ enum OutputFormat {
     Srt,
     Txt,
     Both,
+    Vtt,
+    Json,
 }
 
 #[tauri::command]
@@ -29,6 +39,11 @@ fn transcribe_command(
     output_format: String,
     max_segment_length: u32,
     max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
 ) -> Result<String, String> {
     transcribe_file(
         &model_path,
@@ -36,16 +51,92 @@ fn transcribe_command(
         &output_format,
         max_segment_length,
         max_characters_per_segment,
+        language,
+        translate,
+        split_on_silence,
+        silence_min_gap_ms,
+        silence_threshold_margin,
     )
 }
 
+#[tauri::command]
+fn transcribe_batch_command(
+    model_path: String,
+    wav_paths: Vec<String>,
+    output_format: String,
+    max_segment_length: u32,
+    max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
+) -> Result<Vec<Result<String, String>>, String> {
+    transcribe_batch(
+        &model_path,
+        &wav_paths,
+        &output_format,
+        max_segment_length,
+        max_characters_per_segment,
+        language,
+        translate,
+        split_on_silence,
+        silence_min_gap_ms,
+        silence_threshold_margin,
+    )
+}
+
+/// Like `transcribe_command`, but emits a `transcription://segment` event for
+/// every completed segment as the model runs, plus a final
+/// `transcription://done` event once the transcript (or error) is ready.
+#[tauri::command]
+fn transcribe_streaming_command(
+    window: tauri::Window,
+    model_path: String,
+    wav_path: String,
+    output_format: String,
+    max_segment_length: u32,
+    max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
+) -> Result<String, String> {
+    let progress_window = window.clone();
+
+    let result = transcribe_file_streaming(
+        &model_path,
+        &wav_path,
+        &output_format,
+        max_segment_length,
+        max_characters_per_segment,
+        language,
+        translate,
+        split_on_silence,
+        silence_min_gap_ms,
+        silence_threshold_margin,
+        move |event| {
+            let _ = progress_window.emit("transcription://segment", &event);
+        },
+    );
+
+    let _ = window.emit("transcription://done", &result);
+
+    result
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
-        .invoke_handler(tauri::generate_handler![transcribe_command])
+        .invoke_handler(tauri::generate_handler![
+            transcribe_command,
+            transcribe_batch_command,
+            transcribe_streaming_command
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-        
+
 }
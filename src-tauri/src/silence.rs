@@ -0,0 +1,162 @@
+/// A detected span of silence, in centiseconds (whisper's timestamp unit).
+#[derive(Debug, Clone, Copy)]
+pub struct SilenceInterval {
+    pub start_cs: i64,
+    pub end_cs: i64,
+}
+
+/// Frame size for short-time energy analysis: ~20 ms at 16 kHz.
+const FRAME_MS: u64 = 20;
+
+/// Detects intervals of silence at least `min_gap_ms` long in `samples`
+/// (mono, `sample_rate` Hz) using short-time RMS energy.
+///
+/// A frame is considered silent when its energy falls below the 10th
+/// percentile of all frame energies plus `threshold_margin`.
+pub fn detect_silence(
+    samples: &[f32],
+    sample_rate: u32,
+    min_gap_ms: u32,
+    threshold_margin: f32,
+) -> Vec<SilenceInterval> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let frame_len = ((FRAME_MS * sample_rate as u64) / 1000).max(1) as usize;
+    let energies: Vec<f32> = samples
+        .chunks(frame_len)
+        .map(|frame| {
+            let sum_sq: f32 = frame.iter().map(|s| s * s).sum();
+            (sum_sq / frame.len() as f32).sqrt()
+        })
+        .collect();
+
+    let threshold = percentile(&energies, 0.10) + threshold_margin;
+    let min_gap_frames = ((min_gap_ms as u64 * sample_rate as u64) / (frame_len as u64 * 1000)).max(1);
+
+    let mut intervals = Vec::new();
+    let mut silence_start: Option<usize> = None;
+
+    for (i, &energy) in energies.iter().enumerate() {
+        match (energy < threshold, silence_start) {
+            (true, None) => silence_start = Some(i),
+            (false, Some(start)) => {
+                push_interval(&mut intervals, start, i, frame_len, sample_rate, min_gap_frames);
+                silence_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = silence_start {
+        push_interval(
+            &mut intervals,
+            start,
+            energies.len(),
+            frame_len,
+            sample_rate,
+            min_gap_frames,
+        );
+    }
+
+    intervals
+}
+
+fn push_interval(
+    intervals: &mut Vec<SilenceInterval>,
+    start_frame: usize,
+    end_frame: usize,
+    frame_len: usize,
+    sample_rate: u32,
+    min_gap_frames: u64,
+) {
+    if (end_frame - start_frame) as u64 < min_gap_frames {
+        return;
+    }
+    intervals.push(SilenceInterval {
+        start_cs: frame_to_cs(start_frame, frame_len, sample_rate),
+        end_cs: frame_to_cs(end_frame, frame_len, sample_rate),
+    });
+}
+
+fn frame_to_cs(frame_index: usize, frame_len: usize, sample_rate: u32) -> i64 {
+    ((frame_index as u64 * frame_len as u64 * 100) / sample_rate as u64) as i64
+}
+
+fn percentile(values: &[f32], p: f32) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let idx = (((sorted.len() - 1) as f32) * p).round() as usize;
+    sorted[idx]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_returns_nearest_rank_value() {
+        let values = vec![5.0, 1.0, 3.0, 2.0, 4.0];
+        assert_eq!(percentile(&values, 0.0), 1.0);
+        assert_eq!(percentile(&values, 1.0), 5.0);
+        assert_eq!(percentile(&values, 0.5), 3.0);
+    }
+
+    #[test]
+    fn percentile_of_empty_slice_is_zero() {
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn detect_silence_finds_silent_spans_around_loud_audio() {
+        let sample_rate = 1_000;
+        let frame_len = 20; // 20ms frames at 1kHz
+        let silent_frame = vec![0.0f32; frame_len];
+        let loud_frame = vec![1.0f32; frame_len];
+
+        let mut samples = Vec::new();
+        for _ in 0..10 {
+            samples.extend_from_slice(&silent_frame);
+        }
+        for _ in 0..2 {
+            samples.extend_from_slice(&loud_frame);
+        }
+        for _ in 0..10 {
+            samples.extend_from_slice(&silent_frame);
+        }
+
+        let intervals = detect_silence(&samples, sample_rate, 40, 0.01);
+
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].start_cs, 0);
+        assert_eq!(intervals[0].end_cs, 20);
+        assert_eq!(intervals[1].start_cs, 24);
+        assert_eq!(intervals[1].end_cs, 44);
+    }
+
+    #[test]
+    fn detect_silence_drops_spans_shorter_than_min_gap() {
+        let sample_rate = 1_000;
+        let frame_len = 20;
+        let silent_frame = vec![0.0f32; frame_len];
+
+        // Only 2 silent frames (40ms) total, but min_gap_ms asks for 100ms.
+        let samples: Vec<f32> = silent_frame
+            .iter()
+            .chain(silent_frame.iter())
+            .copied()
+            .collect();
+
+        let intervals = detect_silence(&samples, sample_rate, 100, 0.01);
+
+        assert!(intervals.is_empty());
+    }
+
+    #[test]
+    fn detect_silence_of_empty_input_is_empty() {
+        assert!(detect_silence(&[], 16_000, 500, 0.01).is_empty());
+    }
+}
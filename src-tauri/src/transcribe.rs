@@ -1,10 +1,264 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
 
+use serde::Serialize;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
+use crate::audio::{self, TARGET_SAMPLE_RATE};
+use crate::silence::{self, SilenceInterval};
+
+/// How far (in centiseconds) a chunk boundary may move to land on a detected
+/// silence interval instead of an arbitrary word/segment edge. 50 cs is half a
+/// second, the same order as typical `silence_min_gap_ms` values (a few
+/// hundred ms), so a boundary only moves to a pause close to it and never to a
+/// silence interval further away.
+const SILENCE_SNAP_WINDOW_CS: i64 = 50;
+
+/// Timing and confidence for a single transcribed word, used by the `vtt` and
+/// `json` output formats.
+#[derive(Debug, Clone, Serialize)]
+pub struct WordInfo {
+    pub word: String,
+    pub start: i64,
+    pub end: i64,
+    pub confidence: f32,
+    /// Whether whisper's token began a new word (vs. continuing the previous
+    /// token as a word-piece or attaching as punctuation). Used to decide
+    /// whether a separator belongs between this word and the previous one.
+    pub leading_space: bool,
+}
+
+/// A single completed segment, emitted live by [`transcribe_file_streaming`]
+/// as whisper finishes each one.
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentEvent {
+    pub index: i32,
+    pub start_cs: i64,
+    pub end_cs: i64,
+    pub text: String,
+}
+
+/// Builds a [`SegmentEvent`] from whisper's raw per-segment callback fields,
+/// trimming the segment text. Kept separate from the `whisper_rs` callback
+/// type so the mapping/trimming logic is unit-testable on its own.
+fn segment_event(index: i32, start_cs: i64, end_cs: i64, text: &str) -> SegmentEvent {
+    SegmentEvent {
+        index,
+        start_cs,
+        end_cs,
+        text: text.trim().to_string(),
+    }
+}
+
+/// A whisper segment after collection, with its word-level timings attached.
+#[derive(Debug, Clone)]
+struct RawSeg {
+    start_cs: i64,
+    end_cs: i64,
+    text: String,
+    words: Vec<WordInfo>,
+}
+
+/// A re-chunked span of one or more segments/words, ready for output.
+#[derive(Debug, Clone)]
+struct Chunk {
+    start_cs: i64,
+    end_cs: i64,
+    text: String,
+    words: Vec<WordInfo>,
+}
+
+/// Re-chunks whisper's own segments according to `max_segment_length` /
+/// `max_characters_per_segment`, merging consecutive segments until a limit
+/// would be exceeded.
+fn rechunk_on_segments(
+    raw_segments: Vec<RawSeg>,
+    max_segment_length_cs: i64,
+    use_duration_limit: bool,
+    max_characters_per_segment: u32,
+    use_char_limit: bool,
+) -> Vec<Chunk> {
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut current: Option<Chunk> = None;
+
+    for seg in raw_segments {
+        if let Some(ref mut chunk) = current {
+            // try to append seg to current chunk (if within limits)
+            let new_start_cs = chunk.start_cs;
+            let new_end_cs = seg.end_cs;
+
+            let duration_ok = if use_duration_limit {
+                let dur_cs = new_end_cs - new_start_cs;
+                dur_cs <= max_segment_length_cs
+            } else {
+                true
+            };
+
+            let new_text = if chunk.text.is_empty() {
+                seg.text.clone()
+            } else {
+                format!("{} {}", chunk.text, seg.text)
+            };
+
+            let chars_ok = if use_char_limit {
+                new_text.chars().count() as u32 <= max_characters_per_segment
+            } else {
+                true
+            };
+
+            if duration_ok && chars_ok {
+                // extend current chunk
+                chunk.end_cs = new_end_cs;
+                chunk.text = new_text;
+                chunk.words.extend(seg.words.clone());
+            } else {
+                // close current chunk and start a new one
+                let finished = std::mem::replace(
+                    chunk,
+                    Chunk {
+                        start_cs: seg.start_cs,
+                        end_cs: seg.end_cs,
+                        text: seg.text.clone(),
+                        words: seg.words.clone(),
+                    },
+                );
+                chunks.push(finished);
+            }
+        } else {
+            // first chunk
+            current = Some(Chunk {
+                start_cs: seg.start_cs,
+                end_cs: seg.end_cs,
+                text: seg.text.clone(),
+                words: seg.words.clone(),
+            });
+        }
+    }
+
+    if let Some(chunk) = current {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+/// Re-chunks at word granularity (falling back to whole segments for any
+/// segment whisper didn't give word timings for), snapping a boundary to the
+/// nearest detected silence interval when one falls within
+/// [`SILENCE_SNAP_WINDOW_CS`] of where the length/char limit would otherwise
+/// cut, so subtitles break at natural pauses instead of mid-sentence.
+fn rechunk_on_words(
+    raw_segments: Vec<RawSeg>,
+    max_segment_length_cs: i64,
+    use_duration_limit: bool,
+    max_characters_per_segment: u32,
+    use_char_limit: bool,
+    silence_intervals: &[SilenceInterval],
+) -> Vec<Chunk> {
+    let words: Vec<WordInfo> = raw_segments
+        .into_iter()
+        .flat_map(|seg| {
+            if seg.words.is_empty() {
+                vec![WordInfo {
+                    word: seg.text,
+                    start: seg.start_cs,
+                    end: seg.end_cs,
+                    confidence: 1.0,
+                    leading_space: true,
+                }]
+            } else {
+                seg.words
+            }
+        })
+        .collect();
+
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut current: Option<Chunk> = None;
+
+    for word in words {
+        if let Some(ref mut chunk) = current {
+            let new_end_cs = word.end;
+
+            let duration_ok = if use_duration_limit {
+                (new_end_cs - chunk.start_cs) <= max_segment_length_cs
+            } else {
+                true
+            };
+
+            let new_text = if word.leading_space {
+                format!("{} {}", chunk.text, word.word)
+            } else {
+                format!("{}{}", chunk.text, word.word)
+            };
+            let chars_ok = if use_char_limit {
+                new_text.chars().count() as u32 <= max_characters_per_segment
+            } else {
+                true
+            };
+
+            if duration_ok && chars_ok {
+                chunk.end_cs = new_end_cs;
+                chunk.text = new_text;
+                chunk.words.push(word);
+            } else {
+                // Clamp so a nearby-but-unrelated silence interval can't snap
+                // `end_cs` before the chunk's own start (or the new chunk's
+                // start after its own end), which would yield a negative-
+                // duration cue.
+                chunk.end_cs = snap_to_silence(chunk.end_cs, silence_intervals).max(chunk.start_cs);
+                let boundary_cs = snap_to_silence(word.start, silence_intervals).min(word.end);
+                let finished = std::mem::replace(
+                    chunk,
+                    Chunk {
+                        start_cs: boundary_cs,
+                        end_cs: word.end,
+                        text: word.word.clone(),
+                        words: vec![word],
+                    },
+                );
+                chunks.push(finished);
+            }
+        } else {
+            current = Some(Chunk {
+                start_cs: word.start,
+                end_cs: word.end,
+                text: word.word.clone(),
+                words: vec![word],
+            });
+        }
+    }
+
+    if let Some(chunk) = current {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+/// Snaps `candidate_cs` to the midpoint of the nearest silence interval within
+/// [`SILENCE_SNAP_WINDOW_CS`], or returns it unchanged if none is close enough.
+fn snap_to_silence(candidate_cs: i64, intervals: &[SilenceInterval]) -> i64 {
+    intervals
+        .iter()
+        .map(|interval| (interval.start_cs + interval.end_cs) / 2)
+        .filter(|mid| (mid - candidate_cs).abs() <= SILENCE_SNAP_WINDOW_CS)
+        .min_by_key(|mid| (mid - candidate_cs).abs())
+        .unwrap_or(candidate_cs)
+}
+
 fn format_timestamp(cs: i64) -> String {
+    format_timestamp_with_separator(cs, ',')
+}
+
+fn format_vtt_timestamp(cs: i64) -> String {
+    format_timestamp_with_separator(cs, '.')
+}
+
+fn format_timestamp_with_separator(cs: i64, separator: char) -> String {
     let total_ms = cs * 10; // centiseconds -> ms
 
     let hours = total_ms / 3_600_000;
@@ -16,15 +270,55 @@ fn format_timestamp(cs: i64) -> String {
     let seconds = rem_ms / 1_000;
     let millis = rem_ms % 1_000;
 
-    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, seconds, millis)
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        hours, minutes, seconds, separator, millis
+    )
 }
 
-fn derive_output_paths(wav_path: &str, write_srt: bool, write_txt: bool) -> (Option<PathBuf>, Option<PathBuf>) {
+/// Renders a cue's text for WebVTT, inserting a `<HH:MM:SS.mmm>` tag before
+/// every word after the first so players can highlight along as it's spoken.
+/// The tag follows the previous word directly; a space is only inserted after
+/// the tag if whisper marked the word as starting a new word (`leading_space`);
+/// word-pieces and punctuation attach directly.
+fn build_vtt_cue_text(words: &[WordInfo], fallback: &str) -> String {
+    if words.is_empty() {
+        return fallback.to_string();
+    }
+
+    let mut out = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            out.push('<');
+            out.push_str(&format_vtt_timestamp(word.start));
+            out.push('>');
+            if word.leading_space {
+                out.push(' ');
+            }
+        }
+        out.push_str(&word.word);
+    }
+    out
+}
+
+struct OutputPaths {
+    srt: Option<PathBuf>,
+    txt: Option<PathBuf>,
+    vtt: Option<PathBuf>,
+    json: Option<PathBuf>,
+}
+
+fn derive_output_paths(
+    wav_path: &str,
+    write_srt: bool,
+    write_txt: bool,
+    write_vtt: bool,
+    write_json: bool,
+) -> OutputPaths {
     /*
     @Param wav_path: path to the input WAV file
-    @Param write_srt: whether to generate SRT file
-    @Param write_txt: whether to generate TXT file
-    @Returns: (Option<PathBuf> for SRT, Option<PathBuf> for TXT
+    @Param write_srt/write_txt/write_vtt/write_json: which formats to generate
+    @Returns: an OutputPaths with one Option<PathBuf> per requested format
 
     The output files are placed in the same directory as the input WAV
      */
@@ -32,20 +326,14 @@ fn derive_output_paths(wav_path: &str, write_srt: bool, write_txt: bool) -> (Opt
     let stem = p.file_stem().unwrap_or_default();
 
     let parent = p.parent().unwrap_or_else(|| Path::new("."));
+    let path_for = |ext: &str| parent.join(format!("{}.{}", stem.to_string_lossy(), ext));
 
-    let srt = if write_srt {
-        Some(parent.join(format!("{}.srt", stem.to_string_lossy())))
-    } else {
-        None
-    };
-
-    let txt = if write_txt {
-        Some(parent.join(format!("{}.txt", stem.to_string_lossy())))
-    } else {
-        None
-    };
-
-    (srt, txt)
+    OutputPaths {
+        srt: write_srt.then(|| path_for("srt")),
+        txt: write_txt.then(|| path_for("txt")),
+        vtt: write_vtt.then(|| path_for("vtt")),
+        json: write_json.then(|| path_for("json")),
+    }
 }
 
 pub fn transcribe_file2(
@@ -67,39 +355,249 @@ pub fn transcribe_file2(
 
 /// Pure Rust function you can call from a Tauri command.
 ///
-/// `output_format`: "srt", "txt", or "both".
+/// `output_format`: "srt", "txt", "both", "vtt", or "json".
 /// `max_segment_length`: maximum segment duration in seconds (0 = no limit).
 /// `max_characters_per_segment`: max characters per segment (0 = no limit).
+/// `language`: a whisper language code (e.g. "de"), `None`, or `Some("auto")`
+/// to let whisper detect the spoken language itself.
+/// `translate`: when true, whisper translates the audio to English instead of
+/// transcribing it in the source language.
+/// `split_on_silence`: when true, chunks are built word by word instead of
+/// from whisper's own segments, and each boundary is snapped to a detected
+/// silence interval when one lies within [`SILENCE_SNAP_WINDOW_CS`]; otherwise
+/// it stays on the word edge. `silence_min_gap_ms`/`silence_threshold_margin` tune that
+/// detection (see [`crate::silence::detect_silence`]).
 ///
-/// Returns the full plain-text transcript as a String (for UI),
-/// and writes SRT/TXT files next to `wav_path` when requested.
+/// Returns the full plain-text transcript as a String (for UI), prefixed with
+/// the detected/target audio rate and the detected language code, and writes
+/// SRT/TXT/VTT/JSON files next to `wav_path` when requested. VTT output
+/// includes word-level timestamps; JSON output includes word-level timestamps
+/// and confidences.
 pub fn transcribe_file(
     model_path: &str,
     wav_path: &str,
     output_format: &str,
     max_segment_length: u32,
     max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
+) -> Result<String, String> {
+    let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+        .map_err(|e| format!("Failed to load model: {e}"))?;
+
+    transcribe_with_context(
+        &ctx,
+        wav_path,
+        output_format,
+        max_segment_length,
+        max_characters_per_segment,
+        language,
+        translate,
+        split_on_silence,
+        silence_min_gap_ms,
+        silence_threshold_margin,
+        None,
+    )
+}
+
+/// Like [`transcribe_file`], but reports progress as it happens instead of
+/// blocking until the whole file is done.
+///
+/// `on_segment` is called once per completed whisper segment (in order), so a
+/// caller (typically a Tauri command) can forward it to the frontend as an
+/// event and drive a live progress bar off `end_cs` vs. total audio duration.
+pub fn transcribe_file_streaming(
+    model_path: &str,
+    wav_path: &str,
+    output_format: &str,
+    max_segment_length: u32,
+    max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
+    on_segment: impl FnMut(SegmentEvent) + Send + 'static,
+) -> Result<String, String> {
+    let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+        .map_err(|e| format!("Failed to load model: {e}"))?;
+
+    transcribe_with_context(
+        &ctx,
+        wav_path,
+        output_format,
+        max_segment_length,
+        max_characters_per_segment,
+        language,
+        translate,
+        split_on_silence,
+        silence_min_gap_ms,
+        silence_threshold_margin,
+        Some(Box::new(on_segment)),
+    )
+}
+
+/// Transcribes a batch of WAV files concurrently, loading the (multi-hundred-MB)
+/// model only once and sharing it across a bounded pool of worker threads.
+///
+/// `WhisperContext` is immutable and safe to share across threads; each worker
+/// calls `ctx.create_state()` to get its own mutable state for its file. Results
+/// are returned in the same order as `wav_paths`, with per-file success/failure
+/// kept independent so one bad file doesn't fail the whole batch. `language`
+/// and `translate` apply to every file in the batch.
+pub fn transcribe_batch(
+    model_path: &str,
+    wav_paths: &[String],
+    output_format: &str,
+    max_segment_length: u32,
+    max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
+) -> Result<Vec<Result<String, String>>, String> {
+    let ctx = Arc::new(
+        WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+            .map_err(|e| format!("Failed to load model: {e}"))?,
+    );
+
+    if wav_paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let worker_count = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(wav_paths.len());
+
+    let output_format = output_format.to_string();
+    Ok(run_batch(
+        wav_paths.to_vec(),
+        worker_count,
+        move |wav_path: &String| {
+            transcribe_with_context(
+                &ctx,
+                wav_path,
+                &output_format,
+                max_segment_length,
+                max_characters_per_segment,
+                language.clone(),
+                translate,
+                split_on_silence,
+                silence_min_gap_ms,
+                silence_threshold_margin,
+                None,
+            )
+        },
+    ))
+}
+
+/// Runs `process_one` over `items` across a bounded pool of `worker_count`
+/// worker threads, preserving `items`' order in the returned `Vec` regardless
+/// of completion order. A panic inside `process_one` for one item is caught
+/// and turned into an `Err` for just that item's slot, so one bad item can
+/// never take down the rest of the batch.
+fn run_batch<T, F>(items: Vec<T>, worker_count: usize, process_one: F) -> Vec<Result<String, String>>
+where
+    T: std::fmt::Display + Send + 'static,
+    F: Fn(&T) -> Result<String, String> + Send + Sync + 'static,
+{
+    let len = items.len();
+    let work: Vec<(usize, T)> = items.into_iter().enumerate().collect();
+    let work = Arc::new(Mutex::new(work));
+    let process_one = Arc::new(process_one);
+    let (tx, rx) = mpsc::channel();
+
+    let mut handles = Vec::with_capacity(worker_count);
+    for _ in 0..worker_count {
+        let work = Arc::clone(&work);
+        let process_one = Arc::clone(&process_one);
+        let tx = tx.clone();
+
+        handles.push(thread::spawn(move || loop {
+            let next = work.lock().unwrap().pop();
+            let Some((index, item)) = next else {
+                break;
+            };
+
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| process_one(&item)))
+                .unwrap_or_else(|e| Err(format!("Panicked while processing {item}: {}", panic_message(&e))));
+
+            if tx.send((index, result)).is_err() {
+                break;
+            }
+        }));
+    }
+    drop(tx);
+
+    let mut results: Vec<Option<Result<String, String>>> = vec![None; len];
+    for (index, result) in rx {
+        results[index] = Some(result);
+    }
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    results
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err("Worker thread exited before sending a result".to_string())))
+        .collect()
+}
+
+/// Extracts a human-readable message from a caught panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Transcribes a single WAV file using an already-loaded `WhisperContext`.
+///
+/// This is the shared core behind both [`transcribe_file`] and
+/// [`transcribe_batch`]: the context (and the underlying model weights) is
+/// loaded by the caller, this function only creates a per-run `state`.
+fn transcribe_with_context(
+    ctx: &WhisperContext,
+    wav_path: &str,
+    output_format: &str,
+    max_segment_length: u32,
+    max_characters_per_segment: u32,
+    language: Option<String>,
+    translate: bool,
+    split_on_silence: bool,
+    silence_min_gap_ms: u32,
+    silence_threshold_margin: f32,
+    on_segment: Option<Box<dyn FnMut(SegmentEvent) + Send>>,
 ) -> Result<String, String> {
     let write_srt = matches!(output_format, "srt" | "both");
     let write_txt = matches!(output_format, "txt" | "both");
+    let write_vtt = output_format == "vtt";
+    let write_json = output_format == "json";
 
-    if !write_srt && !write_txt {
+    if !write_srt && !write_txt && !write_vtt && !write_json {
         return Err(format!(
-            "Invalid output format: {}. Use \"srt\", \"txt\", or \"both\".",
+            "Invalid output format: {}. Use \"srt\", \"txt\", \"both\", \"vtt\", or \"json\".",
             output_format
         ));
     }
 
-    // 1) Read WAV
-    let samples: Vec<i16> = hound::WavReader::open(wav_path)
-        .map_err(|e| format!("Failed to open wav: {e}"))?
-        .into_samples::<i16>()
-        .map(|x| x.map_err(|e| format!("Failed to read sample: {e}")))
-        .collect::<Result<Vec<_>, _>>()?;
+    // 1) Read WAV, downmix to mono, and resample to what whisper expects
+    let (inter_samples, source_sample_rate) = audio::load_and_resample(wav_path)?;
+    println!(
+        "  Audio: {} Hz -> {} Hz",
+        source_sample_rate, TARGET_SAMPLE_RATE
+    );
 
-    // 2) Load model
-    let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
-        .map_err(|e| format!("Failed to load model: {e}"))?;
+    // 2) Create a per-run state against the (already-loaded) model
     let mut state = ctx
         .create_state()
         .map_err(|e| format!("Failed to create state: {e}"))?;
@@ -109,140 +607,150 @@ pub fn transcribe_file(
         beam_size: 5,
         patience: -1.0,
     });
-    params.set_language(Some("en"));
+    match language.as_deref() {
+        None | Some("auto") => params.set_language(None),
+        Some(lang) => params.set_language(Some(lang)),
+    }
+    params.set_translate(translate);
     params.set_print_special(false);
     params.set_print_progress(true);
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
+    params.set_token_timestamps(true);
+
+    if let Some(mut on_segment) = on_segment {
+        params.set_segment_callback_safe(move |data: whisper_rs::SegmentCallbackData| {
+            on_segment(segment_event(
+                data.segment,
+                data.start_timestamp,
+                data.end_timestamp,
+                &data.text,
+            ));
+        });
+    }
 
-    // 4) Convert audio to f32 mono 16k (assuming input is already mono 16k PCM)
-    let mut inter_samples = vec![0.0f32; samples.len()];
-    whisper_rs::convert_integer_to_float_audio(&samples, &mut inter_samples)
-        .map_err(|e| format!("Failed to convert audio: {e}"))?;
-
-    // 5) Run model
+    // 4) Run model
     state
         .full(params, &inter_samples[..])
         .map_err(|e| format!("Failed to run model: {e}"))?;
 
-    // 6) Collect raw segments from whisper
-    #[derive(Debug, Clone)]
-    struct RawSeg {
-        start_cs: i64,
-        end_cs: i64,
-        text: String,
-    }
+    let detected_language = whisper_rs::whisper_lang_str(state.full_lang_id()).to_string();
 
+    // 5) Collect raw segments (and their words) from whisper
     let mut raw_segments: Vec<RawSeg> = Vec::new();
-    for segment in state.as_iter() {
+    for (i, segment) in state.as_iter().enumerate() {
         let text = segment.to_string();
         let trimmed = text.trim();
         if trimmed.is_empty() {
             continue;
         }
+
+        let mut words = Vec::new();
+        let token_count = state.full_get_token_count(i as i32);
+        for j in 0..token_count {
+            let token_text = state
+                .full_get_token_text(i as i32, j)
+                .unwrap_or_default();
+            let leading_space = token_text.starts_with(char::is_whitespace);
+            let token_text = token_text.trim();
+            // skip special/control tokens like "[_BEG_]" or "[_TT_123]"
+            if token_text.is_empty() || (token_text.starts_with('[') && token_text.ends_with(']'))
+            {
+                continue;
+            }
+
+            let data = state.full_get_token_data(i as i32, j);
+            words.push(WordInfo {
+                word: token_text.to_string(),
+                start: data.t0,
+                end: data.t1,
+                confidence: data.p,
+                leading_space,
+            });
+        }
+
         raw_segments.push(RawSeg {
             start_cs: segment.start_timestamp(),
             end_cs: segment.end_timestamp(),
             text: trimmed.to_string(),
+            words,
         });
     }
 
-    // 7) Re-chunk segments according to max_segment_length / max_characters_per_segment
-    #[derive(Debug, Clone)]
-    struct Chunk {
-        start_cs: i64,
-        end_cs: i64,
-        text: String,
-    }
-
+    // 6) Re-chunk according to max_segment_length / max_characters_per_segment,
+    //    optionally snapping boundaries to detected silence
     let use_duration_limit = max_segment_length > 0;
     let max_segment_length_cs: i64 = (max_segment_length as i64) * 100; // seconds -> centiseconds
     let use_char_limit = max_characters_per_segment > 0;
 
-    let mut chunks: Vec<Chunk> = Vec::new();
-    let mut current: Option<Chunk> = None;
-
-    for seg in raw_segments {
-        if let Some(ref mut chunk) = current {
-            // try to append seg to current chunk (if within limits)
-            let new_start_cs = chunk.start_cs;
-            let new_end_cs = seg.end_cs;
-
-            let duration_ok = if use_duration_limit {
-                let dur_cs = new_end_cs - new_start_cs;
-                dur_cs <= max_segment_length_cs
-            } else {
-                true
-            };
-
-            let new_text = if chunk.text.is_empty() {
-                seg.text.clone()
-            } else {
-                format!("{} {}", chunk.text, seg.text)
-            };
-
-            let chars_ok = if use_char_limit {
-                new_text.chars().count() as u32 <= max_characters_per_segment
-            } else {
-                true
-            };
-
-            if duration_ok && chars_ok {
-                // extend current chunk
-                chunk.end_cs = new_end_cs;
-                chunk.text = new_text;
-            } else {
-                // close current chunk and start a new one
-                let finished = std::mem::replace(
-                    chunk,
-                    Chunk {
-                        start_cs: seg.start_cs,
-                        end_cs: seg.end_cs,
-                        text: seg.text.clone(),
-                    },
-                );
-                chunks.push(finished);
-            }
-        } else {
-            // first chunk
-            current = Some(Chunk {
-                start_cs: seg.start_cs,
-                end_cs: seg.end_cs,
-                text: seg.text.clone(),
-            });
-        }
-    }
-
-    if let Some(chunk) = current {
-        chunks.push(chunk);
-    }
+    let chunks = if split_on_silence {
+        let silence_intervals = silence::detect_silence(
+            &inter_samples,
+            TARGET_SAMPLE_RATE,
+            silence_min_gap_ms,
+            silence_threshold_margin,
+        );
+        rechunk_on_words(
+            raw_segments,
+            max_segment_length_cs,
+            use_duration_limit,
+            max_characters_per_segment,
+            use_char_limit,
+            &silence_intervals,
+        )
+    } else {
+        rechunk_on_segments(
+            raw_segments,
+            max_segment_length_cs,
+            use_duration_limit,
+            max_characters_per_segment,
+            use_char_limit,
+        )
+    };
 
-    // 8) Prepare output files
-    let (srt_path, txt_path) = derive_output_paths(wav_path, write_srt, write_txt);
-    let mut srt_file = if let Some(ref p) = srt_path {
+    // 7) Prepare output files
+    let output_paths = derive_output_paths(wav_path, write_srt, write_txt, write_vtt, write_json);
+    let mut srt_file = if let Some(ref p) = output_paths.srt {
         Some(File::create(p).map_err(|e| format!("Failed to create SRT: {e}"))?)
     } else {
         None
     };
 
-    let mut txt_file = if let Some(ref p) = txt_path {
+    let mut txt_file = if let Some(ref p) = output_paths.txt {
         Some(File::create(p).map_err(|e| format!("Failed to create TXT: {e}"))?)
     } else {
         None
     };
 
-    // 9) Write chunks + build full_text
-    let mut full_text = String::new();
+    let mut vtt_file = if let Some(ref p) = output_paths.vtt {
+        let mut f = File::create(p).map_err(|e| format!("Failed to create VTT: {e}"))?;
+        writeln!(f, "WEBVTT\n").map_err(|e| format!("Failed to write VTT: {e}"))?;
+        Some(f)
+    } else {
+        None
+    };
+
+    // 8) Write chunks + build full_text/json segments
+    #[derive(Debug, Clone, Serialize)]
+    struct JsonSegment {
+        start: i64,
+        end: i64,
+        text: String,
+        words: Vec<WordInfo>,
+    }
+
+    let mut transcript_text = String::new();
+    let mut json_segments: Vec<JsonSegment> = Vec::new();
 
     for (i, chunk) in chunks.iter().enumerate() {
         let start = format_timestamp(chunk.start_cs);
         let end = format_timestamp(chunk.end_cs);
         let text_trimmed = chunk.text.trim();
 
-        if !full_text.is_empty() {
-            full_text.push(' ');
+        if !transcript_text.is_empty() {
+            transcript_text.push(' ');
         }
-        full_text.push_str(text_trimmed);
+        transcript_text.push_str(text_trimmed);
 
         if let Some(f) = srt_file.as_mut() {
             writeln!(f, "{}", i + 1).map_err(|e| format!("Failed to write SRT: {e}"))?;
@@ -254,7 +762,158 @@ pub fn transcribe_file(
         if let Some(f) = txt_file.as_mut() {
             writeln!(f, "{}", text_trimmed).map_err(|e| format!("Failed to write TXT: {e}"))?;
         }
+
+        if let Some(f) = vtt_file.as_mut() {
+            let vtt_start = format_vtt_timestamp(chunk.start_cs);
+            let vtt_end = format_vtt_timestamp(chunk.end_cs);
+            let cue_text = build_vtt_cue_text(&chunk.words, text_trimmed);
+            writeln!(f, "{} --> {}", vtt_start, vtt_end)
+                .map_err(|e| format!("Failed to write VTT: {e}"))?;
+            writeln!(f, "{}", cue_text).map_err(|e| format!("Failed to write VTT: {e}"))?;
+            writeln!(f).map_err(|e| format!("Failed to write VTT: {e}"))?;
+        }
+
+        if write_json {
+            json_segments.push(JsonSegment {
+                start: chunk.start_cs,
+                end: chunk.end_cs,
+                text: text_trimmed.to_string(),
+                words: chunk.words.clone(),
+            });
+        }
+    }
+
+    if let Some(ref p) = output_paths.json {
+        let payload = serde_json::json!({
+            "result": json_segments,
+            "text": transcript_text,
+            "language": detected_language,
+        });
+        let json_string = serde_json::to_string_pretty(&payload)
+            .map_err(|e| format!("Failed to serialize JSON: {e}"))?;
+        std::fs::write(p, json_string).map_err(|e| format!("Failed to write JSON: {e}"))?;
+    }
+
+    Ok(format!(
+        "[audio: {} Hz -> {} Hz] [language: {}]\n{}",
+        source_sample_rate, TARGET_SAMPLE_RATE, detected_language, transcript_text
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: i64, end: i64, leading_space: bool) -> WordInfo {
+        WordInfo {
+            word: text.to_string(),
+            start,
+            end,
+            confidence: 1.0,
+            leading_space,
+        }
+    }
+
+    #[test]
+    fn build_vtt_cue_text_only_spaces_before_new_words() {
+        let words = vec![
+            word("don", 0, 10, true),
+            word("'t", 10, 20, false),
+            word("stop", 20, 40, true),
+        ];
+        let text = build_vtt_cue_text(&words, "");
+        assert_eq!(
+            text,
+            "don<00:00:00.100>'t<00:00:00.200> stop"
+        );
     }
 
-    Ok(full_text)
-}
\ No newline at end of file
+    #[test]
+    fn build_vtt_cue_text_falls_back_to_plain_text_without_words() {
+        assert_eq!(build_vtt_cue_text(&[], "hello"), "hello");
+    }
+
+    #[test]
+    fn snap_to_silence_prefers_nearest_interval_within_window() {
+        let intervals = vec![
+            SilenceInterval {
+                start_cs: 100,
+                end_cs: 110,
+            }, // midpoint 105
+            SilenceInterval {
+                start_cs: 200,
+                end_cs: 220,
+            }, // midpoint 210
+        ];
+        assert_eq!(snap_to_silence(107, &intervals), 105);
+        // outside the snap window: unchanged
+        assert_eq!(snap_to_silence(500, &intervals), 500);
+    }
+
+    #[test]
+    fn rechunk_on_words_splits_on_char_limit_and_snaps_boundary_to_silence() {
+        let raw = vec![RawSeg {
+            start_cs: 0,
+            end_cs: 50,
+            text: "hello world".to_string(),
+            words: vec![word("hello", 0, 18, true), word("world", 22, 50, true)],
+        }];
+        let silence_intervals = vec![SilenceInterval {
+            start_cs: 19,
+            end_cs: 21,
+        }];
+
+        let chunks = rechunk_on_words(raw, 0, false, 5, true, &silence_intervals);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "hello");
+        assert_eq!(chunks[1].text, "world");
+        // both boundaries should have snapped to the silence interval's midpoint
+        assert_eq!(chunks[0].end_cs, 20);
+        assert_eq!(chunks[1].start_cs, 20);
+    }
+
+    #[test]
+    fn run_batch_preserves_order_despite_out_of_order_completion() {
+        let items: Vec<u32> = (0..8).collect();
+        let results = run_batch(items, 4, |n: &u32| {
+            // Earlier items sleep longer, so later items tend to finish first.
+            std::thread::sleep(std::time::Duration::from_millis((8 - n) as u64));
+            Ok(format!("item-{n}"))
+        });
+
+        let expected: Vec<Result<String, String>> =
+            (0..8).map(|n| Ok(format!("item-{n}"))).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn run_batch_isolates_a_panic_to_the_offending_item() {
+        let items: Vec<u32> = vec![1, 2, 3];
+        let results = run_batch(items, 2, |n: &u32| {
+            if *n == 2 {
+                panic!("boom");
+            }
+            Ok(format!("ok-{n}"))
+        });
+
+        assert_eq!(results[0], Ok("ok-1".to_string()));
+        assert!(results[1].is_err());
+        assert_eq!(results[2], Ok("ok-3".to_string()));
+    }
+
+    #[test]
+    fn run_batch_of_empty_items_is_empty() {
+        let results = run_batch(Vec::<u32>::new(), 4, |_: &u32| Ok(String::new()));
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn segment_event_trims_text_and_maps_fields() {
+        let event = segment_event(3, 100, 250, "  hello world  ");
+        assert_eq!(event.index, 3);
+        assert_eq!(event.start_cs, 100);
+        assert_eq!(event.end_cs, 250);
+        assert_eq!(event.text, "hello world");
+    }
+}